@@ -1,10 +1,15 @@
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use anyhow::{anyhow, Result};
 use bytes::Bytes;
 use dashmap::{mapref::entry, DashMap};
 use lunatic_process::{env::Environment, state::ProcessState};
 use quinn::{ClientConfig, Connecting, ConnectionError, Endpoint, ServerConfig};
+use rcgen::{CertificateParams, DistinguishedName, DnType};
 use rustls::server::AllowAnyAuthenticatedClient;
 use rustls_pemfile::Item;
 use wasmtime::ResourceLimiter;
@@ -14,6 +19,65 @@ use crate::{
     DistributedCtx,
 };
 
+// A self-signed certificate authority used to sign node identities for a cluster.
+// generate_node_identity signs a leaf certificate with it. to_pem exports the CA
+// certificate and key, but a CaKeyPair cannot be rebuilt from PEM: leaf
+// certificates can only be minted in the process that called generate.
+pub struct CaKeyPair {
+    cert: rcgen::Certificate,
+}
+
+impl CaKeyPair {
+    pub fn generate() -> Result<Self> {
+        let mut params = CertificateParams::new(vec![]);
+        let mut dn = DistinguishedName::new();
+        dn.push(DnType::CommonName, "lunatic-cluster-ca");
+        params.distinguished_name = dn;
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        let cert = rcgen::Certificate::from_params(params)?;
+        Ok(CaKeyPair { cert })
+    }
+
+    pub fn to_pem(&self) -> Result<(String, String)> {
+        let cert_pem = self.cert.serialize_pem()?;
+        let key_pem = self.cert.serialize_private_key_pem();
+        Ok((cert_pem, key_pem))
+    }
+}
+
+// Generates a PEM-encoded certificate/key pair for node_name, signed by ca.
+pub fn generate_node_identity(node_name: &str, ca: &CaKeyPair) -> Result<(String, String)> {
+    let mut params = CertificateParams::new(vec![node_name.to_string()]);
+    let mut dn = DistinguishedName::new();
+    dn.push(DnType::CommonName, node_name);
+    params.distinguished_name = dn;
+    let cert = rcgen::Certificate::from_params(params)?;
+    let cert_pem = cert.serialize_pem_with_signer(&ca.cert)?;
+    let key_pem = cert.serialize_private_key_pem();
+    Ok((cert_pem, key_pem))
+}
+
+// Format version of the chunk header. There is no negotiation: a receiver
+// tears down the stream on any mismatch rather than risk misparsing a header
+// framed a different way, so a rolling upgrade across a version bump here
+// requires draining old-version connections first.
+const CHUNK_FORMAT_VERSION: u8 = 1;
+
+fn crc32_of(data: &[u8]) -> u32 {
+    static CRC32: OnceLock<crc::Crc<u32>> = OnceLock::new();
+    CRC32
+        .get_or_init(|| crc::Crc::<u32>::new(&crc::CRC_32_ISO_HDLC))
+        .checksum(data)
+}
+
+// When LUNATIC_KEYLOG is set, attaches a KeyLogFile writing to SSLKEYLOGFILE
+// so node traffic can be decrypted in Wireshark. Does nothing otherwise.
+fn enable_keylog_if_requested(key_log: &mut Arc<dyn rustls::KeyLog>) {
+    if std::env::var_os("LUNATIC_KEYLOG").is_some() {
+        *key_log = Arc::new(rustls::KeyLogFile::new());
+    }
+}
+
 pub struct SendStream {
     pub stream: quinn::SendStream,
 }
@@ -23,6 +87,31 @@ impl SendStream {
         self.stream.write_all_chunks(data).await?;
         Ok(())
     }
+
+    // Sends a single framed chunk: a one-byte format version, the
+    // message_id | message_size | chunk_id | chunk_size header, data, and a
+    // trailing CRC32 of data.
+    pub async fn send_chunk(
+        &mut self,
+        message_id: u64,
+        message_size: u32,
+        chunk_id: u64,
+        data: &[u8],
+    ) -> Result<()> {
+        let mut header = Vec::with_capacity(1 + 8 + 4 + 8 + 4);
+        header.push(CHUNK_FORMAT_VERSION);
+        header.extend_from_slice(&message_id.to_le_bytes());
+        header.extend_from_slice(&message_size.to_le_bytes());
+        header.extend_from_slice(&chunk_id.to_le_bytes());
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        let crc = crc32_of(data);
+        let mut chunks = [
+            Bytes::from(header),
+            Bytes::copy_from_slice(data),
+            Bytes::copy_from_slice(&crc.to_le_bytes()),
+        ];
+        self.send(&mut chunks).await
+    }
 }
 
 pub struct RecvStream {
@@ -51,7 +140,11 @@ pub struct Client {
 
 impl Client {
     pub async fn _connect(&self, addr: SocketAddr, name: &str) -> Result<quinn::Connection> {
-        Ok(self.inner.connect(addr, name)?.await?)
+        Ok(self
+            .inner
+            .connect(addr, name)?
+            .await
+            .map_err(describe_connection_error)?)
     }
 
     pub async fn try_connect(
@@ -91,13 +184,86 @@ impl Client {
     }
 
     async fn connect_once(&self, addr: SocketAddr, name: &str) -> Result<(SendStream, RecvStream)> {
-        let conn = self.inner.connect(addr, name)?.await?;
+        let conn = self
+            .inner
+            .connect(addr, name)?
+            .await
+            .map_err(describe_connection_error)?;
         let (send, recv) = conn.open_bi().await?;
         Ok((SendStream { stream: send }, RecvStream { stream: recv }))
     }
 }
 
-pub fn new_quic_client(ca_cert: &str, cert: &str, key: &str) -> Result<Client> {
+// Turns a ConnectionError into an anyhow::Error that, for an ApplicationClosed
+// connection, surfaces the remote's close code and message.
+fn describe_connection_error(e: ConnectionError) -> anyhow::Error {
+    match &e {
+        ConnectionError::ApplicationClosed(close) => anyhow!(
+            "remote closed the connection with code {}: {}",
+            close.error_code,
+            String::from_utf8_lossy(&close.reason)
+        ),
+        _ => anyhow!(e),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum CongestionController {
+    Cubic,
+    Bbr,
+}
+
+// Tuning knobs for the quinn::TransportConfig shared by a node's QUIC
+// endpoints, instead of relying on quinn's defaults.
+#[derive(Clone, Copy, Debug)]
+pub struct QuicTransportConfig {
+    pub max_idle_timeout: Duration,
+    pub keep_alive_interval: Duration,
+    pub max_concurrent_bidi_streams: u32,
+    pub stream_receive_window: u32,
+    pub congestion_controller: CongestionController,
+}
+
+impl Default for QuicTransportConfig {
+    fn default() -> Self {
+        QuicTransportConfig {
+            max_idle_timeout: Duration::from_secs(60),
+            keep_alive_interval: Duration::from_secs(30),
+            max_concurrent_bidi_streams: 256,
+            stream_receive_window: 1024 * 1024,
+            congestion_controller: CongestionController::Bbr,
+        }
+    }
+}
+
+impl QuicTransportConfig {
+    fn apply(&self, transport: &mut quinn::TransportConfig) -> Result<()> {
+        transport
+            .max_idle_timeout(Some(self.max_idle_timeout.try_into()?))
+            .keep_alive_interval(Some(self.keep_alive_interval))
+            .max_concurrent_bidi_streams(self.max_concurrent_bidi_streams.into())
+            .stream_receive_window(self.stream_receive_window.into());
+        match self.congestion_controller {
+            CongestionController::Bbr => {
+                transport
+                    .congestion_controller_factory(Arc::new(quinn::congestion::BbrConfig::default()));
+            }
+            CongestionController::Cubic => {
+                transport.congestion_controller_factory(Arc::new(
+                    quinn::congestion::CubicConfig::default(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn new_quic_client(
+    ca_cert: &str,
+    cert: &str,
+    key: &str,
+    transport: QuicTransportConfig,
+) -> Result<Client> {
     let mut ca_cert = ca_cert.as_bytes();
     let ca_cert = rustls_pemfile::read_one(&mut ca_cert)?.unwrap();
     let ca_cert = match ca_cert {
@@ -121,18 +287,27 @@ pub fn new_quic_client(ca_cert: &str, cert: &str, key: &str) -> Result<Client> {
     }?;
     let cert = vec![cert];
 
-    let client_crypto = rustls::ClientConfig::builder()
+    let mut client_crypto = rustls::ClientConfig::builder()
         .with_safe_defaults()
         .with_root_certificates(roots)
         .with_single_cert(cert, pk)?;
+    enable_keylog_if_requested(&mut client_crypto.key_log);
 
-    let client_config = ClientConfig::new(Arc::new(client_crypto));
+    let mut client_config = ClientConfig::new(Arc::new(client_crypto));
+    transport.apply(Arc::get_mut(&mut client_config.transport).unwrap())?;
     let mut endpoint = Endpoint::client("[::]:0".parse().unwrap())?;
     endpoint.set_default_client_config(client_config);
     Ok(Client { inner: endpoint })
 }
 
-pub fn new_quic_server(addr: SocketAddr, cert: &str, key: &str, ca_cert: &str) -> Result<Endpoint> {
+pub fn new_quic_server(
+    addr: SocketAddr,
+    cert: &str,
+    key: &str,
+    ca_cert: &str,
+    transport: QuicTransportConfig,
+    enable_retry: bool,
+) -> Result<Endpoint> {
     let mut ca_cert = ca_cert.as_bytes();
     let ca_cert = rustls_pemfile::read_one(&mut ca_cert)?.unwrap();
     let ca_cert = match ca_cert {
@@ -155,28 +330,82 @@ pub fn new_quic_server(addr: SocketAddr, cert: &str, key: &str, ca_cert: &str) -
         _ => Err(anyhow!("Not a valid certificate")),
     }?;
     let cert = vec![cert];
-    let server_crypto = rustls::ServerConfig::builder()
+    let mut server_crypto = rustls::ServerConfig::builder()
         .with_safe_defaults()
         .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
         .with_single_cert(cert, pk)?;
+    enable_keylog_if_requested(&mut server_crypto.key_log);
     let mut server_config = ServerConfig::with_crypto(Arc::new(server_crypto));
-    Arc::get_mut(&mut server_config.transport)
-        .unwrap()
-        .max_concurrent_uni_streams(0_u8.into());
+    let server_transport = Arc::get_mut(&mut server_config.transport).unwrap();
+    server_transport.max_concurrent_uni_streams(0_u8.into());
+    transport.apply(server_transport)?;
+    // When enable_retry is set, requires a source to round-trip a Retry token
+    // before the server allocates handshake state, to defend against
+    // amplification floods.
+    server_config.use_retry(enable_retry);
 
     Ok(quinn::Endpoint::server(server_config, addr)?)
 }
 
+// Application error codes a node uses when closing a connection; only reasons
+// this file actually puts on the wire are listed.
+#[derive(Clone, Copy, Debug)]
+pub enum NodeCloseReason {
+    ProtocolViolation,
+    MessageTooLarge,
+}
+
+impl NodeCloseReason {
+    fn code(self) -> quinn::VarInt {
+        let code: u32 = match self {
+            NodeCloseReason::ProtocolViolation => 0,
+            NodeCloseReason::MessageTooLarge => 1,
+        };
+        quinn::VarInt::from_u32(code)
+    }
+
+    fn reason(self) -> &'static [u8] {
+        match self {
+            NodeCloseReason::ProtocolViolation => b"protocol violation",
+            NodeCloseReason::MessageTooLarge => b"message too large",
+        }
+    }
+}
+
+// Bounds on message reassembly in RecvCtx, so a peer-supplied message_size or
+// a dribbling sender can't grow the receiver's memory without limit.
+#[derive(Clone, Copy)]
+pub struct NodeTransportLimits {
+    // Largest message_size a chunk is allowed to declare.
+    pub max_message_size: usize,
+    // Largest number of messages that may be reassembling concurrently.
+    pub max_in_flight_messages: usize,
+    // How long to wait between chunks of a reassembling message before giving
+    // up, and also the total time a message may spend reassembling.
+    pub message_timeout: Duration,
+}
+
+impl Default for NodeTransportLimits {
+    fn default() -> Self {
+        NodeTransportLimits {
+            max_message_size: 64 * 1024 * 1024,
+            max_in_flight_messages: 64,
+            message_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
 pub async fn handle_node_server<T, E>(
     quic_server: &mut Endpoint,
     ctx: distributed::server::ServerCtx<T, E>,
+    limits: NodeTransportLimits,
 ) -> Result<()>
 where
     T: ProcessState + ResourceLimiter + DistributedCtx<E> + Send + Sync + 'static,
     E: Environment + 'static,
 {
     while let Some(conn) = quic_server.accept().await {
-        tokio::spawn(handle_quic_connection_node(ctx.clone(), conn));
+        tokio::spawn(handle_quic_connection_node(ctx.clone(), conn, limits));
     }
     Err(anyhow!("Node server exited"))
 }
@@ -184,6 +413,7 @@ where
 async fn handle_quic_connection_node<T, E>(
     ctx: distributed::server::ServerCtx<T, E>,
     conn: Connecting,
+    limits: NodeTransportLimits,
 ) -> Result<()>
 where
     T: ProcessState + ResourceLimiter + DistributedCtx<E> + Send + Sync + 'static,
@@ -203,10 +433,26 @@ where
             Ok((s, r)) => {
                 let send = SendStream { stream: s };
                 let recv = RecvStream { stream: r };
-                tokio::spawn(handle_quic_stream_node(ctx.clone(), send, recv));
+                tokio::spawn(handle_quic_stream_node(
+                    ctx.clone(),
+                    send,
+                    recv,
+                    limits,
+                    conn.clone(),
+                ));
             }
             Err(ConnectionError::LocallyClosed) => break,
-            Err(_) => {}
+            Err(e) => {
+                log::warn!(
+                    "Error accepting stream from {}: {e}",
+                    conn.remote_address()
+                );
+                conn.close(
+                    NodeCloseReason::ProtocolViolation.code(),
+                    NodeCloseReason::ProtocolViolation.reason(),
+                );
+                break;
+            }
         }
     }
     log::info!("Connection from remote {} closed", conn.remote_address());
@@ -217,6 +463,8 @@ async fn handle_quic_stream_node<T, E>(
     ctx: distributed::server::ServerCtx<T, E>,
     mut send: SendStream,
     recv: RecvStream,
+    limits: NodeTransportLimits,
+    conn: quinn::Connection,
 ) where
     T: ProcessState + ResourceLimiter + DistributedCtx<E> + Send + Sync + 'static,
     E: Environment + 'static,
@@ -224,8 +472,26 @@ async fn handle_quic_stream_node<T, E>(
     let mut recv_ctx = RecvCtx {
         recv: recv.stream,
         chunks: DashMap::new(),
+        limits,
     };
-    while let Ok((msg_id, bytes)) = read_next_stream_message(&mut recv_ctx).await {
+    loop {
+        let (msg_id, bytes) = match read_next_stream_message(&mut recv_ctx).await {
+            Ok(message) => message,
+            Err(e) if e.downcast_ref::<StreamEnded>().is_some() => break,
+            Err(e) => {
+                let reason = if e.downcast_ref::<MessageTooLarge>().is_some() {
+                    NodeCloseReason::MessageTooLarge
+                } else {
+                    NodeCloseReason::ProtocolViolation
+                };
+                log::warn!(
+                    "Closing connection to {}: {e}",
+                    conn.remote_address()
+                );
+                conn.close(reason.code(), reason.reason());
+                break;
+            }
+        };
         if let Ok(request) = rmp_serde::from_slice::<distributed::message::Request>(&bytes) {
             distributed::server::handle_message(ctx.clone(), &mut send, msg_id, request).await;
         } else {
@@ -242,11 +508,60 @@ struct Chunk {
 
 struct RecvCtx {
     recv: quinn::RecvStream,
-    // Map to collect message chunks key: message_id, data: (message_size, data)
-    chunks: DashMap<u64, (usize, Vec<u8>)>,
+    // Map to collect message chunks key: message_id, data: (message_size, data, first chunk's arrival time)
+    chunks: DashMap<u64, (usize, Vec<u8>, std::time::Instant)>,
+    limits: NodeTransportLimits,
+}
+
+// Marker error: the stream ended cleanly between chunks, not mid-frame.
+#[derive(Debug)]
+struct StreamEnded;
+
+impl std::fmt::Display for StreamEnded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "stream ended")
+    }
+}
+
+impl std::error::Error for StreamEnded {}
+
+// Marker error: a chunk declared a message_size over the configured limit.
+#[derive(Debug)]
+struct MessageTooLarge {
+    message_id: u64,
+    message_size: usize,
+    limit: usize,
+}
+
+impl std::fmt::Display for MessageTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "message_id={} declares message_size={}, exceeding the {}-byte limit",
+            self.message_id, self.message_size, self.limit
+        )
+    }
 }
 
-async fn read_next_stream_chunk(recv: &mut quinn::RecvStream) -> Result<Chunk> {
+impl std::error::Error for MessageTooLarge {}
+
+async fn read_next_stream_chunk(
+    recv: &mut quinn::RecvStream,
+    limits: NodeTransportLimits,
+) -> Result<Chunk> {
+    // Read and check the format version before trusting the rest of the header
+    let mut version = [0u8; 1];
+    match recv.read_exact(&mut version).await {
+        Ok(()) => {}
+        Err(quinn::ReadExactError::FinishedEarly) => return Err(StreamEnded.into()),
+        Err(e) => return Err(anyhow!("{e} failed to read header version")),
+    }
+    let version = version[0];
+    if version != CHUNK_FORMAT_VERSION {
+        return Err(anyhow!(
+            "rejecting chunk format version {version}, only {CHUNK_FORMAT_VERSION} is supported"
+        ));
+    }
     // Read chunk header info
     let mut message_id = [0u8; 8];
     let mut message_size = [0u8; 4];
@@ -268,11 +583,35 @@ async fn read_next_stream_chunk(recv: &mut quinn::RecvStream) -> Result<Chunk> {
     let message_size = u32::from_le_bytes(message_size) as usize;
     let chunk_id = u64::from_le_bytes(chunk_id);
     let chunk_size = u32::from_le_bytes(chunk_size) as usize;
+    // Validate declared sizes against the configured limit *before* allocating
+    // `data`, otherwise a forged chunk_size alone (e.g. 0xFFFF_FFFF) would OOM
+    // the receiver ahead of any check.
+    if message_size > limits.max_message_size {
+        return Err(MessageTooLarge {
+            message_id,
+            message_size,
+            limit: limits.max_message_size,
+        }
+        .into());
+    }
+    if chunk_size > message_size {
+        return Err(anyhow!(
+            "message_id={message_id} chunk_id={chunk_id} declares chunk_size={chunk_size} \
+             exceeding its own message_size={message_size}"
+        ));
+    }
     // Read chunk data
     let mut data = vec![0u8; chunk_size];
     recv.read_exact(&mut data)
         .await
         .map_err(|e| anyhow!("{e} failed to read message body"))?;
+    // Read and verify the trailing CRC32 so a bit-flip or a desynced chunk_size
+    // is caught here instead of silently corrupting the rmp_serde-decoded Request
+    let mut crc_bytes = [0u8; 4];
+    recv.read_exact(&mut crc_bytes)
+        .await
+        .map_err(|e| anyhow!("{e} failed to read chunk crc32"))?;
+    verify_chunk_crc(message_id, chunk_id, &data, u32::from_le_bytes(crc_bytes))?;
     log::trace!("read message_id={message_id} chunk_id={chunk_id}");
     Ok(Chunk {
         message_id,
@@ -281,16 +620,62 @@ async fn read_next_stream_chunk(recv: &mut quinn::RecvStream) -> Result<Chunk> {
     })
 }
 
+fn verify_chunk_crc(message_id: u64, chunk_id: u64, data: &[u8], expected_crc: u32) -> Result<()> {
+    let actual_crc = crc32_of(data);
+    if actual_crc != expected_crc {
+        return Err(anyhow!(
+            "CRC32 mismatch for chunk_id={chunk_id} of message_id={message_id}: \
+             expected {expected_crc:#x}, got {actual_crc:#x}"
+        ));
+    }
+    Ok(())
+}
+
 async fn read_next_stream_message(ctx: &mut RecvCtx) -> Result<(u64, Bytes)> {
     loop {
-        let new_chunk = read_next_stream_chunk(&mut ctx.recv).await?;
+        // Only bound the wait while a message is reassembling, not an idle
+        // connection. message_timeout also caps the total reassembly time,
+        // so a slow drip of chunks can't keep a message alive forever.
+        let new_chunk = if ctx.chunks.is_empty() {
+            read_next_stream_chunk(&mut ctx.recv, ctx.limits).await?
+        } else {
+            let timeout = ctx.limits.message_timeout;
+            tokio::time::timeout(timeout, read_next_stream_chunk(&mut ctx.recv, ctx.limits))
+                .await
+                .map_err(|_| {
+                    anyhow!("timed out after {timeout:?} waiting for a stalled message to finish")
+                })??
+        };
         let message_id = new_chunk.message_id;
         let message_size = new_chunk.message_size;
+        // message_size/chunk_size are already validated against limits.max_message_size
+        // in read_next_stream_chunk, before it allocates the chunk's data buffer.
         if let Some(mut entry) = ctx.chunks.get_mut(&message_id) {
+            if entry.2.elapsed() > ctx.limits.message_timeout {
+                return Err(anyhow!(
+                    "message_id={message_id} took longer than {:?} to reassemble, \
+                     rejecting the stalled message",
+                    ctx.limits.message_timeout
+                ));
+            }
+            if entry.1.len() + new_chunk.data.len() > entry.0 {
+                return Err(anyhow!(
+                    "message_id={message_id} overshot its declared message_size={message_size}"
+                ));
+            }
             entry.1.extend(new_chunk.data);
         } else {
-            ctx.chunks
-                .insert(message_id, (message_size, new_chunk.data));
+            if ctx.chunks.len() >= ctx.limits.max_in_flight_messages {
+                return Err(anyhow!(
+                    "refusing message_id={message_id}: {} messages already in flight, limit is {}",
+                    ctx.chunks.len(),
+                    ctx.limits.max_in_flight_messages
+                ));
+            }
+            ctx.chunks.insert(
+                message_id,
+                (message_size, new_chunk.data, std::time::Instant::now()),
+            );
         };
         let finished = ctx
             .chunks
@@ -309,3 +694,121 @@ async fn read_next_stream_message(ctx: &mut RecvCtx) -> Result<(u64, Bytes)> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncWriteExt;
+
+    #[test]
+    fn crc32_rejects_a_mutated_chunk_byte() {
+        let data = b"a chunk of an rmp_serde-encoded Request".to_vec();
+        let crc = crc32_of(&data);
+        assert!(verify_chunk_crc(1, 0, &data, crc).is_ok());
+
+        let mut corrupted = data;
+        corrupted[0] ^= 0xff;
+        assert!(verify_chunk_crc(1, 0, &corrupted, crc).is_err());
+    }
+
+    #[tokio::test]
+    async fn read_next_stream_chunk_rejects_a_corrupted_chunk_over_the_wire() {
+        let ca = CaKeyPair::generate().unwrap();
+        let (ca_cert, _ca_key) = ca.to_pem().unwrap();
+        let (server_cert, server_key) = generate_node_identity("test-server", &ca).unwrap();
+        let (client_cert, client_key) = generate_node_identity("test-client", &ca).unwrap();
+
+        let mut server_endpoint = new_quic_server(
+            "127.0.0.1:0".parse().unwrap(),
+            &server_cert,
+            &server_key,
+            &ca_cert,
+            QuicTransportConfig::default(),
+            false,
+        )
+        .unwrap();
+        let server_addr = server_endpoint.local_addr().unwrap();
+
+        let client = new_quic_client(
+            &ca_cert,
+            &client_cert,
+            &client_key,
+            QuicTransportConfig::default(),
+        )
+        .unwrap();
+
+        let server_task = tokio::spawn(async move {
+            let conn = server_endpoint.accept().await.unwrap().await.unwrap();
+            let (_send, mut recv) = conn.accept_bi().await.unwrap();
+            read_next_stream_chunk(&mut recv, NodeTransportLimits::default()).await
+        });
+
+        let conn = client._connect(server_addr, "test-server").await.unwrap();
+        let (mut send, _recv) = conn.open_bi().await.unwrap();
+
+        let data = b"a chunk of an rmp_serde-encoded Request".to_vec();
+        let crc = crc32_of(&data);
+        let mut corrupted = data.clone();
+        corrupted[0] ^= 0xff;
+
+        let mut header = Vec::with_capacity(1 + 8 + 4 + 8 + 4);
+        header.push(CHUNK_FORMAT_VERSION);
+        header.extend_from_slice(&1u64.to_le_bytes()); // message_id
+        header.extend_from_slice(&(data.len() as u32).to_le_bytes()); // message_size
+        header.extend_from_slice(&0u64.to_le_bytes()); // chunk_id
+        header.extend_from_slice(&(corrupted.len() as u32).to_le_bytes()); // chunk_size
+
+        send.write_all(&header).await.unwrap();
+        send.write_all(&corrupted).await.unwrap();
+        send.write_all(&crc.to_le_bytes()).await.unwrap();
+        send.finish().await.unwrap();
+
+        let result = server_task.await.unwrap();
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn send_chunk_round_trips_through_read_next_stream_chunk() {
+        let ca = CaKeyPair::generate().unwrap();
+        let (ca_cert, _ca_key) = ca.to_pem().unwrap();
+        let (server_cert, server_key) = generate_node_identity("test-server", &ca).unwrap();
+        let (client_cert, client_key) = generate_node_identity("test-client", &ca).unwrap();
+
+        let mut server_endpoint = new_quic_server(
+            "127.0.0.1:0".parse().unwrap(),
+            &server_cert,
+            &server_key,
+            &ca_cert,
+            QuicTransportConfig::default(),
+            false,
+        )
+        .unwrap();
+        let server_addr = server_endpoint.local_addr().unwrap();
+
+        let client = new_quic_client(
+            &ca_cert,
+            &client_cert,
+            &client_key,
+            QuicTransportConfig::default(),
+        )
+        .unwrap();
+
+        let server_task = tokio::spawn(async move {
+            let conn = server_endpoint.accept().await.unwrap().await.unwrap();
+            let (_send, mut recv) = conn.accept_bi().await.unwrap();
+            read_next_stream_chunk(&mut recv, NodeTransportLimits::default()).await
+        });
+
+        let conn = client._connect(server_addr, "test-server").await.unwrap();
+        let (send, _recv) = conn.open_bi().await.unwrap();
+        let mut send = SendStream { stream: send };
+
+        let data = b"a chunk of an rmp_serde-encoded Request".to_vec();
+        send.send_chunk(1, data.len() as u32, 0, &data).await.unwrap();
+
+        let chunk = server_task.await.unwrap().unwrap();
+        assert_eq!(chunk.message_id, 1);
+        assert_eq!(chunk.message_size, data.len());
+        assert_eq!(chunk.data, data);
+    }
+}